@@ -0,0 +1,168 @@
+//! Use this module to interact with the raw Bitstamp API.
+//! Raw API data will be exposed to caller as a JSON `Value`, which reflects the exchange's
+//! answer as closely as possible.
+
+use std::collections::HashMap;
+
+use reqwest::Client;
+use serde_json::Value;
+
+use bitstamp::utils;
+use error::*;
+use types::{Pair, Volume, Price};
+
+const BITSTAMP_API_URL: &str = "https://www.bitstamp.net/api/v2/";
+
+/// A client for the Bitstamp REST API.
+pub struct BitstampApi {
+    api_key: String,
+    customer_id: String,
+    secret: String,
+    http_client: Client,
+}
+
+impl BitstampApi {
+    /// Creates a new `BitstampApi` authenticated with the given credentials.
+    pub fn new<K, C, S>(api_key: K, customer_id: C, secret: S) -> BitstampApi
+        where K: Into<String>,
+              C: Into<String>,
+              S: Into<String>
+    {
+        BitstampApi {
+            api_key: api_key.into(),
+            customer_id: customer_id.into(),
+            secret: secret.into(),
+            http_client: Client::new(),
+        }
+    }
+
+    /// Sends a GET request to a public endpoint and returns the parsed JSON body.
+    fn public_query(&self, method: &str, _params: &HashMap<&str, String>) -> Result<Value> {
+        let url = format!("{}{}", BITSTAMP_API_URL, method);
+        let mut response = self.http_client
+            .get(&url)
+            .send()
+            .chain_err(|| format!("failed to query Bitstamp endpoint '{}'", method))?;
+
+        response.json().chain_err(|| "Bitstamp response was not valid JSON")
+    }
+
+    /// Sends a signed POST request to a private endpoint and returns the parsed JSON body.
+    fn private_query(&self, method: &str, params: &HashMap<&str, String>) -> Result<Value> {
+        let url = format!("{}{}", BITSTAMP_API_URL, method);
+        let signed_params = utils::sign_params(&self.api_key, &self.customer_id, &self.secret, params);
+
+        let mut response = self.http_client
+            .post(&url)
+            .form(&signed_params)
+            .send()
+            .chain_err(|| format!("failed to query Bitstamp endpoint '{}'", method))?;
+
+        response.json().chain_err(|| "Bitstamp response was not valid JSON")
+    }
+
+    /// Returns the ticker for `pair`.
+    pub fn return_ticker(&mut self, pair: Pair) -> Result<Value> {
+        let pair_name = utils::get_pair_string(&pair).ok_or(ErrorKind::PairUnsupported)?;
+        self.public_query(&format!("ticker/{}/", pair_name), &HashMap::new())
+    }
+
+    /// Returns the order book for `pair`.
+    pub fn return_order_book(&mut self, pair: Pair) -> Result<Value> {
+        let pair_name = utils::get_pair_string(&pair).ok_or(ErrorKind::PairUnsupported)?;
+        self.public_query(&format!("order_book/{}/", pair_name), &HashMap::new())
+    }
+
+    /// Returns the most recent public transactions for `pair`.
+    pub fn return_transactions(&mut self, pair: Pair) -> Result<Value> {
+        let pair_name = utils::get_pair_string(&pair).ok_or(ErrorKind::PairUnsupported)?;
+        self.public_query(&format!("transactions/{}/", pair_name), &HashMap::new())
+    }
+
+    /// Returns the balances for each currency on the account.
+    pub fn return_balances(&mut self) -> Result<Value> {
+        self.private_query("balance/", &HashMap::new())
+    }
+
+    /// Places a limit buy order.
+    pub fn buy_limit(&mut self,
+                      pair: Pair,
+                      quantity: Volume,
+                      price: Price,
+                      limit_price: Option<Price>,
+                      daily_order: Option<bool>)
+                      -> Result<Value> {
+        let pair_name = utils::get_pair_string(&pair).ok_or(ErrorKind::PairUnsupported)?;
+
+        let mut params = HashMap::new();
+        params.insert("amount", quantity.to_string());
+        params.insert("price", price.to_string());
+        if let Some(limit_price) = limit_price {
+            params.insert("limit_price", limit_price.to_string());
+        }
+        if let Some(daily_order) = daily_order {
+            params.insert("daily_order", daily_order.to_string());
+        }
+
+        self.private_query(&format!("buy/{}/", pair_name), &params)
+    }
+
+    /// Places a market buy order.
+    pub fn buy_market(&mut self, pair: Pair, quantity: Volume) -> Result<Value> {
+        let pair_name = utils::get_pair_string(&pair).ok_or(ErrorKind::PairUnsupported)?;
+
+        let mut params = HashMap::new();
+        params.insert("amount", quantity.to_string());
+
+        self.private_query(&format!("buy/market/{}/", pair_name), &params)
+    }
+
+    /// Places a limit sell order.
+    pub fn sell_limit(&mut self,
+                       pair: Pair,
+                       quantity: Volume,
+                       price: Price,
+                       limit_price: Option<Price>,
+                       daily_order: Option<bool>)
+                       -> Result<Value> {
+        let pair_name = utils::get_pair_string(&pair).ok_or(ErrorKind::PairUnsupported)?;
+
+        let mut params = HashMap::new();
+        params.insert("amount", quantity.to_string());
+        params.insert("price", price.to_string());
+        if let Some(limit_price) = limit_price {
+            params.insert("limit_price", limit_price.to_string());
+        }
+        if let Some(daily_order) = daily_order {
+            params.insert("daily_order", daily_order.to_string());
+        }
+
+        self.private_query(&format!("sell/{}/", pair_name), &params)
+    }
+
+    /// Places a market sell order.
+    pub fn sell_market(&mut self, pair: Pair, quantity: Volume) -> Result<Value> {
+        let pair_name = utils::get_pair_string(&pair).ok_or(ErrorKind::PairUnsupported)?;
+
+        let mut params = HashMap::new();
+        params.insert("amount", quantity.to_string());
+
+        self.private_query(&format!("sell/market/{}/", pair_name), &params)
+    }
+
+    /// Cancels an order by id.
+    pub fn cancel_order(&mut self, order_id: String) -> Result<Value> {
+        let mut params = HashMap::new();
+        params.insert("id", order_id);
+
+        self.private_query("cancel_order/", &params)
+    }
+
+    /// Returns the current status of an order by id.
+    pub fn order_status(&mut self, order_id: String) -> Result<Value> {
+        let mut params = HashMap::new();
+        params.insert("id", order_id);
+
+        self.private_query("order_status/", &params)
+    }
+}
@@ -2,6 +2,8 @@
 //! This a more convenient and safe way to deal with the exchange since methods return a Result<>
 //! but this generic API does not provide all the functionnality that Bitstamp offers.
 
+use serde_json::{Map, Value};
+
 use exchange::ExchangeApi;
 use bitstamp::api::BitstampApi;
 use bitstamp::utils;
@@ -12,77 +14,36 @@ use helpers;
 
 impl ExchangeApi for BitstampApi {
     fn ticker(&mut self, pair: Pair) -> Result<Ticker> {
-
         let result = self.return_ticker(pair)?;
-
-        let price = helpers::from_json_float(&result["last"], "last")?;
-        let ask = helpers::from_json_float(&result["ask"], "ask")?;
-        let bid = helpers::from_json_float(&result["bid"], "bid")?;
-        let vol = helpers::from_json_float(&result["volume"], "volume")?;
-
-        Ok(Ticker {
-               timestamp: helpers::get_unix_timestamp_ms(),
-               pair: pair,
-               last_trade_price: price,
-               lowest_ask: ask,
-               highest_bid: bid,
-               volume: Some(vol),
-           })
+        parse_ticker(&result, pair)
     }
 
     fn orderbook(&mut self, pair: Pair) -> Result<Orderbook> {
-
         let raw_response = self.return_order_book(pair)?;
-
         let result = utils::parse_result(&raw_response)?;
+        parse_orderbook(&result, pair)
+    }
 
-        let mut ask_offers = Vec::new();
-        let mut bid_offers = Vec::new();
-
-        let ask_array =
-            result["asks"]
-                .as_array()
-                .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", result["asks"])))?;
-        let bid_array =
-            result["bids"]
-                .as_array()
-                .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", result["asks"])))?;
+    fn trades(&mut self, pair: Pair) -> Result<Vec<Trade>> {
+        let raw_response = self.return_transactions(pair)?;
+        parse_trades(&raw_response, pair)
+    }
 
-        for ask in ask_array {
-            let price = ask[0]
-                .as_str()
-                .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", ask[0])))?
-                .parse::<f64>()
-                .chain_err(|| ErrorKind::InvalidFieldFormat(format!("{}", ask[0])))?;
+    /// Bitstamp has no bulk ticker endpoint, so this issues one `ticker` request per pair in
+    /// `utils::SUPPORTED_PAIRS` and fails the whole batch as soon as one of them does.
+    fn all_tickers(&mut self) -> Result<Vec<Ticker>> {
+        let mut tickers = Vec::new();
 
-            let volume = ask[1]
-                .as_str()
-                .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", ask[1])))?
-                .parse::<f64>()
-                .chain_err(|| ErrorKind::InvalidFieldFormat(format!("{}", ask[1])))?;
-            ask_offers.push((price, volume));
+        for &pair in utils::SUPPORTED_PAIRS {
+            tickers.push(self.ticker(pair)?);
         }
 
-        for bid in bid_array {
-            let price = bid[0]
-                .as_str()
-                .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", bid[0])))?
-                .parse::<f64>()
-                .chain_err(|| ErrorKind::InvalidFieldFormat(format!("{}", bid[0])))?;
-            let volume = bid[1]
-                .as_str()
-                .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", bid[1])))?
-                .parse::<f64>()
-                .chain_err(|| ErrorKind::InvalidFieldFormat(format!("{}", bid[1])))?;
-            bid_offers.push((price, volume));
-        }
+        Ok(tickers)
+    }
 
-        Ok(Orderbook {
-            timestamp: helpers::get_unix_timestamp_ms(),
-            pair: pair,
-            asks: ask_offers,
-            bids: bid_offers,
-        })
+    fn book_ticker(&mut self, pair: Pair) -> Result<(Price, Volume, Price, Volume)> {
+        let orderbook = self.orderbook(pair)?;
+        top_of_book(orderbook)
     }
 
     fn add_order(&mut self,
@@ -96,22 +57,16 @@ impl ExchangeApi for BitstampApi {
         //None => return Err(ErrorKind::PairUnsupported.into()),
         //};
 
+        validate_order(order_type, &price)?;
+
         let result = match order_type {
             OrderType::BuyLimit => {
-                if price.is_none() {
-                    return Err(ErrorKind::MissingPrice.into());
-                }
-
-                // Unwrap safe here with the check above.
+                // Unwrap safe here: validate_order checked price is Some above.
                 self.buy_limit(pair, quantity, price.unwrap(), None, None)
             }
             OrderType::BuyMarket => self.buy_market(pair, quantity),
             OrderType::SellLimit => {
-                if price.is_none() {
-                    return Err(ErrorKind::MissingPrice.into());
-                }
-
-                // Unwrap safe here with the check above.
+                // Unwrap safe here: validate_order checked price is Some above.
                 self.sell_limit(pair, quantity, price.unwrap(), None, None)
             }
             OrderType::SellMarket => self.sell_market(pair, quantity),
@@ -128,6 +83,34 @@ impl ExchangeApi for BitstampApi {
            })
     }
 
+    fn add_order_dry_run(&mut self,
+                          order_type: OrderType,
+                          pair: Pair,
+                          _quantity: Volume,
+                          price: Option<Price>)
+                          -> Result<OrderInfo> {
+        validate_order(order_type, &price)?;
+        utils::get_pair_string(&pair).ok_or(ErrorKind::PairUnsupported)?;
+
+        Ok(OrderInfo {
+               timestamp: helpers::get_unix_timestamp_ms(),
+               identifier: vec!["dry_run".to_string()],
+           })
+    }
+
+    fn cancel_order(&mut self, order_id: String, _pair: Option<Pair>) -> Result<bool> {
+        let raw_response = self.cancel_order(order_id)?;
+        let result = utils::parse_result(&raw_response)?;
+
+        Ok(result.get("error").is_none())
+    }
+
+    fn order_status(&mut self, order_id: String) -> Result<OrderStatus> {
+        let raw_response = self.order_status(order_id)?;
+        let result = utils::parse_result(&raw_response)?;
+        parse_order_status(&result)
+    }
+
     /// Return the balances for each currency on the account
     fn balances(&mut self) -> Result<Balances> {
         let raw_response = self.return_balances()?;
@@ -142,7 +125,7 @@ impl ExchangeApi for BitstampApi {
                 Some(c) => {
                     let amount = val.as_str()
                         .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", val)))?
-                        .parse::<f64>()
+                        .parse::<Volume>()
                         .chain_err(|| ErrorKind::InvalidFieldFormat(format!("{}", val)))?;
 
                     balances.insert(c, amount);
@@ -154,3 +137,319 @@ impl ExchangeApi for BitstampApi {
         Ok(balances)
     }
 }
+
+/// Checks that `price` is present whenever `order_type` requires a limit price, so both
+/// `add_order` and `add_order_dry_run` reject the same malformed requests.
+fn validate_order(order_type: OrderType, price: &Option<Price>) -> Result<()> {
+    match order_type {
+        OrderType::BuyLimit | OrderType::SellLimit => {
+            if price.is_none() {
+                return Err(ErrorKind::MissingPrice.into());
+            }
+        }
+        OrderType::BuyMarket | OrderType::SellMarket => (),
+    }
+
+    Ok(())
+}
+
+/// Extracts the best bid/ask price and volume off the top of `orderbook`.
+fn top_of_book(orderbook: Orderbook) -> Result<(Price, Volume, Price, Volume)> {
+    let (best_bid_price, best_bid_volume) = orderbook.bids
+        .into_iter()
+        .next()
+        .ok_or_else(|| ErrorKind::InvalidFieldFormat("bids".to_string()))?;
+    let (best_ask_price, best_ask_volume) = orderbook.asks
+        .into_iter()
+        .next()
+        .ok_or_else(|| ErrorKind::InvalidFieldFormat("asks".to_string()))?;
+
+    Ok((best_bid_price, best_bid_volume, best_ask_price, best_ask_volume))
+}
+
+/// Parses a raw `order_status/` response into an `OrderStatus`.
+fn parse_order_status(result: &Map<String, Value>) -> Result<OrderStatus> {
+    let status = result["status"]
+        .as_str()
+        .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{:?}", result)))?;
+
+    match status {
+        "Open" => Ok(OrderStatus::Open),
+        "Finished" => Ok(OrderStatus::Filled),
+        "Canceled" | "Cancelled" => Ok(OrderStatus::Cancelled),
+        "Partially Filled" => {
+            let filled = result["amount_filled"]
+                .as_str()
+                .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{:?}", result)))?
+                .parse::<Volume>()
+                .chain_err(|| ErrorKind::InvalidFieldFormat(format!("{:?}", result)))?;
+            Ok(OrderStatus::PartiallyFilled(filled))
+        }
+        other => Err(ErrorKind::InvalidFieldFormat(other.to_string()).into()),
+    }
+}
+
+/// Parses a raw `transactions/` response into a list of `Trade`s.
+fn parse_trades(raw_response: &Value, pair: Pair) -> Result<Vec<Trade>> {
+    let transactions = raw_response
+        .as_array()
+        .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", raw_response)))?;
+
+    let mut trades = Vec::new();
+
+    for transaction in transactions {
+        let timestamp = transaction["date"]
+            .as_str()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", transaction["date"])))?
+            .parse::<u64>()
+            .chain_err(|| ErrorKind::InvalidFieldFormat(format!("{}", transaction["date"])))?;
+
+        let price = transaction["price"]
+            .as_str()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", transaction["price"])))?
+            .parse::<Price>()
+            .chain_err(|| ErrorKind::InvalidFieldFormat(format!("{}", transaction["price"])))?;
+
+        let amount = transaction["amount"]
+            .as_str()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", transaction["amount"])))?
+            .parse::<Volume>()
+            .chain_err(|| ErrorKind::InvalidFieldFormat(format!("{}", transaction["amount"])))?;
+
+        let trade_type = match transaction["type"].as_str() {
+            Some("0") => TradeType::Buy,
+            Some("1") => TradeType::Sell,
+            _ => {
+                return Err(ErrorKind::InvalidFieldFormat(format!("{}", transaction["type"]))
+                               .into())
+            }
+        };
+
+        trades.push(Trade {
+                        timestamp: timestamp * 1000,
+                        pair: pair,
+                        price: price,
+                        amount: amount,
+                        trade_type: trade_type,
+                    });
+    }
+
+    Ok(trades)
+}
+
+/// Parses a raw `ticker/` response into a `Ticker`.
+pub(crate) fn parse_ticker(result: &Value, pair: Pair) -> Result<Ticker> {
+    let price: Price = helpers::from_json_float(&result["last"], "last")?;
+    let ask: Price = helpers::from_json_float(&result["ask"], "ask")?;
+    let bid: Price = helpers::from_json_float(&result["bid"], "bid")?;
+    let vol: Volume = helpers::from_json_float(&result["volume"], "volume")?;
+
+    Ok(Ticker {
+           timestamp: helpers::get_unix_timestamp_ms(),
+           pair: pair,
+           last_trade_price: price,
+           lowest_ask: ask,
+           highest_bid: bid,
+           volume: Some(vol),
+       })
+}
+
+/// Parses a raw `order_book/` response into an `Orderbook`.
+///
+/// Pulled out of `ExchangeApi::orderbook` so `bitstamp::streaming_api` can apply the exact
+/// same field extraction to messages pushed over the live feed.
+pub(crate) fn parse_orderbook(result: &Map<String, Value>, pair: Pair) -> Result<Orderbook> {
+    let mut ask_offers = Vec::new();
+    let mut bid_offers = Vec::new();
+
+    let ask_array =
+        result["asks"]
+            .as_array()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", result["asks"])))?;
+    let bid_array =
+        result["bids"]
+            .as_array()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", result["asks"])))?;
+
+    for ask in ask_array {
+        let price = ask[0]
+            .as_str()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", ask[0])))?
+            .parse::<Price>()
+            .chain_err(|| ErrorKind::InvalidFieldFormat(format!("{}", ask[0])))?;
+
+        let volume = ask[1]
+            .as_str()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", ask[1])))?
+            .parse::<Volume>()
+            .chain_err(|| ErrorKind::InvalidFieldFormat(format!("{}", ask[1])))?;
+        ask_offers.push((price, volume));
+    }
+
+    for bid in bid_array {
+        let price = bid[0]
+            .as_str()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", bid[0])))?
+            .parse::<Price>()
+            .chain_err(|| ErrorKind::InvalidFieldFormat(format!("{}", bid[0])))?;
+        let volume = bid[1]
+            .as_str()
+            .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", bid[1])))?
+            .parse::<Volume>()
+            .chain_err(|| ErrorKind::InvalidFieldFormat(format!("{}", bid[1])))?;
+        bid_offers.push((price, volume));
+    }
+
+    Ok(Orderbook {
+           timestamp: helpers::get_unix_timestamp_ms(),
+           pair: pair,
+           asks: ask_offers,
+           bids: bid_offers,
+       })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_ticker_reads_last_ask_bid_and_volume() {
+        let result: Value = ::serde_json::from_str(r#"{
+            "last": "9000.50", "ask": "9001.00", "bid": "9000.00", "volume": "123.45"
+        }"#)
+                .unwrap();
+
+        let ticker = parse_ticker(&result, Pair::BTC_USD).unwrap();
+
+        assert_eq!(ticker.pair, Pair::BTC_USD);
+        assert_eq!(ticker.last_trade_price, "9000.50".parse::<Price>().unwrap());
+        assert_eq!(ticker.lowest_ask, "9001.00".parse::<Price>().unwrap());
+        assert_eq!(ticker.highest_bid, "9000.00".parse::<Price>().unwrap());
+        assert_eq!(ticker.volume, Some("123.45".parse::<Volume>().unwrap()));
+    }
+
+    #[test]
+    fn add_order_dry_run_rejects_a_missing_price() {
+        let mut api = BitstampApi::new("key", "customer_id", "secret");
+
+        let result = api.add_order_dry_run(OrderType::BuyLimit,
+                                            Pair::BTC_USD,
+                                            "1.0".parse().unwrap(),
+                                            None);
+
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn add_order_dry_run_returns_a_synthetic_identifier() {
+        let mut api = BitstampApi::new("key", "customer_id", "secret");
+
+        let order = api.add_order_dry_run(OrderType::BuyMarket,
+                                           Pair::BTC_USD,
+                                           "1.0".parse().unwrap(),
+                                           None)
+            .unwrap();
+
+        assert_eq!(order.identifier, vec!["dry_run".to_string()]);
+    }
+
+    #[test]
+    fn parse_orderbook_reads_asks_and_bids_in_order() {
+        let result: Map<String, Value> = ::serde_json::from_str(r#"{
+            "asks": [["9001.00", "1.5"], ["9002.00", "2.0"]],
+            "bids": [["9000.00", "0.5"]]
+        }"#)
+                .unwrap();
+
+        let orderbook = parse_orderbook(&result, Pair::BTC_USD).unwrap();
+
+        assert_eq!(orderbook.pair, Pair::BTC_USD);
+        assert_eq!(orderbook.asks.len(), 2);
+        assert_eq!(orderbook.asks[0].0, "9001.00".parse::<Price>().unwrap());
+        assert_eq!(orderbook.bids[0].1, "0.5".parse::<Volume>().unwrap());
+    }
+
+    #[test]
+    fn top_of_book_reads_best_bid_and_ask() {
+        let orderbook = Orderbook {
+            timestamp: 0,
+            pair: Pair::BTC_USD,
+            asks: vec![("9001.00".parse().unwrap(), "1.5".parse().unwrap()),
+                       ("9002.00".parse().unwrap(), "2.0".parse().unwrap())],
+            bids: vec![("9000.00".parse().unwrap(), "0.5".parse().unwrap())],
+        };
+
+        let (bid_price, bid_volume, ask_price, ask_volume) = top_of_book(orderbook).unwrap();
+
+        assert_eq!(bid_price, "9000.00".parse::<Price>().unwrap());
+        assert_eq!(bid_volume, "0.5".parse::<Volume>().unwrap());
+        assert_eq!(ask_price, "9001.00".parse::<Price>().unwrap());
+        assert_eq!(ask_volume, "1.5".parse::<Volume>().unwrap());
+    }
+
+    #[test]
+    fn top_of_book_errors_on_an_empty_side() {
+        let orderbook = Orderbook {
+            timestamp: 0,
+            pair: Pair::BTC_USD,
+            asks: vec![("9001.00".parse().unwrap(), "1.5".parse().unwrap())],
+            bids: vec![],
+        };
+
+        assert!(top_of_book(orderbook).is_err());
+    }
+
+    #[test]
+    fn parse_order_status_maps_every_known_state() {
+        let open: Map<String, Value> = ::serde_json::from_str(r#"{"status": "Open"}"#).unwrap();
+        assert_eq!(parse_order_status(&open).unwrap(), OrderStatus::Open);
+
+        let finished: Map<String, Value> = ::serde_json::from_str(r#"{"status": "Finished"}"#)
+            .unwrap();
+        assert_eq!(parse_order_status(&finished).unwrap(), OrderStatus::Filled);
+
+        let cancelled: Map<String, Value> = ::serde_json::from_str(r#"{"status": "Canceled"}"#)
+            .unwrap();
+        assert_eq!(parse_order_status(&cancelled).unwrap(), OrderStatus::Cancelled);
+
+        let partial: Map<String, Value> =
+            ::serde_json::from_str(r#"{"status": "Partially Filled", "amount_filled": "0.5"}"#)
+                .unwrap();
+        assert_eq!(parse_order_status(&partial).unwrap(),
+                   OrderStatus::PartiallyFilled(Volume::from(0.5)));
+    }
+
+    #[test]
+    fn parse_order_status_rejects_unknown_state() {
+        let result: Map<String, Value> = ::serde_json::from_str(r#"{"status": "Queued"}"#)
+            .unwrap();
+        assert!(parse_order_status(&result).is_err());
+    }
+
+    #[test]
+    fn parse_trades_maps_buy_and_sell_sides() {
+        let raw_response: Value = ::serde_json::from_str(r#"[
+            {"date": "1500000000", "price": "9000.50", "amount": "0.01", "type": "0", "tid": "1"},
+            {"date": "1500000001", "price": "9001.00", "amount": "0.02", "type": "1", "tid": "2"}
+        ]"#)
+                .unwrap();
+
+        let trades = parse_trades(&raw_response, Pair::BTC_USD).unwrap();
+
+        assert_eq!(trades.len(), 2);
+        assert_eq!(trades[0].timestamp, 1500000000 * 1000);
+        assert_eq!(trades[0].pair, Pair::BTC_USD);
+        assert_eq!(trades[0].trade_type, TradeType::Buy);
+        assert_eq!(trades[1].trade_type, TradeType::Sell);
+    }
+
+    #[test]
+    fn parse_trades_rejects_unknown_type() {
+        let raw_response: Value = ::serde_json::from_str(r#"[
+            {"date": "1500000000", "price": "9000.50", "amount": "0.01", "type": "2", "tid": "1"}
+        ]"#)
+                .unwrap();
+
+        assert!(parse_trades(&raw_response, Pair::BTC_USD).is_err());
+    }
+}
@@ -0,0 +1,8 @@
+//! Bitstamp exchange implementation.
+
+pub mod api;
+pub mod generic_api;
+pub mod streaming_api;
+pub mod utils;
+
+pub use self::api::BitstampApi;
@@ -0,0 +1,206 @@
+//! Live ticker and order book updates for Bitstamp, over its Pusher WebSocket channels.
+//!
+//! Each subscription spawns a background thread that keeps a WebSocket connection open,
+//! decodes incoming messages with the same field-extraction logic as `generic_api`, and
+//! forwards them to the caller over a channel. A message older than the last one already
+//! delivered is dropped, so a consumer reading from the `Receiver` never acts on a stale quote.
+//! A message that fails to decode is forwarded as an `Err` rather than dropped, so the caller
+//! can tell a parsing problem apart from there simply being no new quote yet.
+
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+
+use bigdecimal::BigDecimal;
+use serde_json::Value;
+use tungstenite::{connect, Message};
+
+use bitstamp::api::BitstampApi;
+use bitstamp::generic_api::parse_orderbook;
+use bitstamp::utils;
+use error::*;
+use streaming::StreamingApi;
+use types::*;
+
+const BITSTAMP_WS_URL: &str = "wss://ws.bitstamp.net";
+
+impl StreamingApi for BitstampApi {
+    // Bitstamp's Pusher feed has no channel carrying last-price/bid/ask together (its
+    // `live_trades_*` channel only pushes individual trade ticks), so the ticker is derived
+    // from the same `order_book_*` channel `subscribe_orderbook` uses, the way `book_ticker`
+    // derives its snapshot from `orderbook`.
+    fn subscribe_ticker(&mut self, pair: Pair) -> Result<Receiver<Result<Ticker>>> {
+        let channel = order_book_channel(pair)?;
+        let (sender, receiver) = mpsc::channel();
+
+        spawn_subscription(channel, sender, move |payload| {
+            let result = payload.as_object()
+                .cloned()
+                .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", payload)))?;
+            let orderbook = parse_orderbook(&result, pair)?;
+            let ticker = ticker_from_orderbook(orderbook)?;
+            Ok((ticker.timestamp, ticker))
+        });
+
+        Ok(receiver)
+    }
+
+    fn subscribe_orderbook(&mut self, pair: Pair) -> Result<Receiver<Result<Orderbook>>> {
+        let channel = order_book_channel(pair)?;
+        let (sender, receiver) = mpsc::channel();
+
+        spawn_subscription(channel, sender, move |payload| {
+            let result = payload.as_object()
+                .cloned()
+                .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", payload)))?;
+            let orderbook = parse_orderbook(&result, pair)?;
+            Ok((orderbook.timestamp, orderbook))
+        });
+
+        Ok(receiver)
+    }
+}
+
+fn order_book_channel(pair: Pair) -> Result<String> {
+    let pair_name = utils::get_pair_string(&pair).ok_or(ErrorKind::PairUnsupported)?;
+    Ok(format!("order_book_{}", pair_name))
+}
+
+/// Unwraps a raw Pusher message into the payload of a `data` event on `channel`, or `None` if
+/// `text` isn't JSON, is a protocol frame (subscription ack, heartbeat, ...), is a `data` event
+/// for a different channel (channels are multiplexed over one socket), or its `data` isn't the
+/// JSON-encoded-string payload Bitstamp nests it as.
+fn unwrap_pusher_envelope(text: &str, channel: &str) -> Option<Value> {
+    let envelope: Value = ::serde_json::from_str(text).ok()?;
+
+    if envelope["event"].as_str() != Some("data") {
+        return None;
+    }
+    if envelope["channel"].as_str() != Some(channel) {
+        return None;
+    }
+
+    envelope["data"].as_str().and_then(|data| ::serde_json::from_str(data).ok())
+}
+
+/// Builds a `Ticker` snapshot out of the top of an order book, the last trade price being
+/// approximated as the mid-point between the best bid and ask (the order book channel carries
+/// no trade price of its own).
+fn ticker_from_orderbook(orderbook: Orderbook) -> Result<Ticker> {
+    let (best_bid, _) = orderbook.bids
+        .into_iter()
+        .next()
+        .ok_or_else(|| ErrorKind::InvalidFieldFormat("bids".to_string()))?;
+    let (best_ask, _) = orderbook.asks
+        .into_iter()
+        .next()
+        .ok_or_else(|| ErrorKind::InvalidFieldFormat("asks".to_string()))?;
+
+    let mid = Price((best_bid.0.clone() + best_ask.0.clone()) / BigDecimal::from(2));
+
+    Ok(Ticker {
+           timestamp: orderbook.timestamp,
+           pair: orderbook.pair,
+           last_trade_price: mid,
+           lowest_ask: best_ask,
+           highest_bid: best_bid,
+           volume: None,
+       })
+}
+
+/// Spawns the background thread backing a single subscription: connects to `channel` on
+/// Bitstamp's WebSocket endpoint, unwraps Pusher's message envelope, decodes the payload of
+/// every `data` event on `channel` with `parse`, and forwards the result to `sender` unless
+/// its timestamp is older than the last one already sent.
+///
+/// A message `parse` fails to decode is forwarded to `sender` as an `Err` rather than dropped,
+/// so a schema change or a decoding bug is visible to the caller instead of looking identical
+/// to "no new quotes yet"; the subscription keeps running afterwards.
+fn spawn_subscription<T, F>(channel: String, sender: Sender<Result<T>>, parse: F)
+    where T: Send + 'static,
+          F: Fn(Value) -> Result<(u64, T)> + Send + 'static
+{
+    thread::spawn(move || {
+        let (mut socket, _) = match connect(BITSTAMP_WS_URL) {
+            Ok(connection) => connection,
+            Err(_) => return,
+        };
+
+        let subscribe = format!(r#"{{"event":"pusher:subscribe","data":{{"channel":"{}"}}}}"#,
+                                 channel);
+        if socket.write_message(Message::Text(subscribe)).is_err() {
+            return;
+        }
+
+        let mut last_timestamp = 0u64;
+
+        loop {
+            let message = match socket.read_message() {
+                Ok(message) => message,
+                Err(_) => return,
+            };
+
+            let text = match message {
+                Message::Text(text) => text,
+                _ => continue,
+            };
+
+            let payload = match unwrap_pusher_envelope(&text, &channel) {
+                Some(payload) => payload,
+                None => continue,
+            };
+
+            let (timestamp, item) = match parse(payload) {
+                Ok(parsed) => parsed,
+                Err(e) => {
+                    if sender.send(Err(e)).is_err() {
+                        // The consumer dropped the `Receiver`; nothing left to do.
+                        return;
+                    }
+                    continue;
+                }
+            };
+
+            if timestamp < last_timestamp {
+                // Stale quote: a newer message has already been delivered.
+                continue;
+            }
+            last_timestamp = timestamp;
+
+            if sender.send(Ok(item)).is_err() {
+                // The consumer dropped the `Receiver`; nothing left to do.
+                return;
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn unwrap_pusher_envelope_decodes_a_data_event_for_our_channel() {
+        let text = r#"{"event":"data","channel":"order_book_btcusd","data":"{\"bids\":[]}"}"#;
+
+        let payload = unwrap_pusher_envelope(text, "order_book_btcusd").unwrap();
+
+        assert_eq!(payload["bids"].as_array().unwrap().len(), 0);
+    }
+
+    #[test]
+    fn unwrap_pusher_envelope_ignores_protocol_frames() {
+        let text = r#"{"event":"pusher:connection_established","data":"{}"}"#;
+        assert!(unwrap_pusher_envelope(text, "order_book_btcusd").is_none());
+    }
+
+    #[test]
+    fn unwrap_pusher_envelope_ignores_other_channels() {
+        let text = r#"{"event":"data","channel":"order_book_ethusd","data":"{\"bids\":[]}"}"#;
+        assert!(unwrap_pusher_envelope(text, "order_book_btcusd").is_none());
+    }
+
+    #[test]
+    fn unwrap_pusher_envelope_rejects_non_json_text() {
+        assert!(unwrap_pusher_envelope("not json", "order_book_btcusd").is_none());
+    }
+}
@@ -0,0 +1,124 @@
+//! Small Bitstamp-specific helpers shared by `api` and `generic_api`.
+
+use std::collections::HashMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use hmac::{Hmac, Mac};
+use serde_json::{Map, Value};
+use sha2::Sha256;
+
+use error::*;
+use types::{Currency, Pair};
+
+type HmacSha256 = Hmac<Sha256>;
+
+/// Every pair Bitstamp supports, for callers that need to sweep the whole market
+/// (e.g. `ExchangeApi::all_tickers`).
+pub const SUPPORTED_PAIRS: &[Pair] = &[Pair::BTC_USD,
+                                        Pair::BTC_EUR,
+                                        Pair::ETH_USD,
+                                        Pair::ETH_EUR,
+                                        Pair::ETH_BTC];
+
+/// Returns the Bitstamp pair name (e.g. `"btcusd"`) for `pair`, if Bitstamp supports it.
+pub fn get_pair_string(pair: &Pair) -> Option<&'static str> {
+    match *pair {
+        Pair::BTC_USD => Some("btcusd"),
+        Pair::BTC_EUR => Some("btceur"),
+        Pair::ETH_USD => Some("ethusd"),
+        Pair::ETH_EUR => Some("etheur"),
+        Pair::ETH_BTC => Some("ethbtc"),
+    }
+}
+
+/// Returns the `Currency` matching a Bitstamp balance key (e.g. `"btc_balance"`), if known.
+pub fn get_currency_enum(key: &str) -> Option<Currency> {
+    match key {
+        "btc_balance" => Some(Currency::BTC),
+        "eth_balance" => Some(Currency::ETH),
+        "usd_balance" => Some(Currency::USD),
+        "eur_balance" => Some(Currency::EUR),
+        _ => None,
+    }
+}
+
+/// Signs `params` with the account's API key, customer id and secret, the way Bitstamp's
+/// private endpoints require: a nonce, plus `signature = HMAC_SHA256(secret, nonce +
+/// customer_id + api_key)`, upper-cased hex.
+pub fn sign_params<'a>(api_key: &'a str,
+                        customer_id: &'a str,
+                        secret: &str,
+                        params: &HashMap<&'a str, String>)
+                        -> HashMap<&'a str, String> {
+    let nonce = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .expect("system clock is before Unix epoch")
+        .as_secs()
+        .to_string();
+    let message = format!("{}{}{}", nonce, customer_id, api_key);
+
+    let mut mac = HmacSha256::new_from_slice(secret.as_bytes())
+        .expect("HMAC accepts a key of any size");
+    mac.update(message.as_bytes());
+
+    let signature = mac.finalize()
+        .into_bytes()
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<String>();
+
+    let mut signed = params.clone();
+    signed.insert("key", api_key.to_string());
+    signed.insert("nonce", nonce);
+    signed.insert("signature", signature);
+    signed
+}
+
+/// Extracts the JSON object from a raw Bitstamp response, failing if Bitstamp reported an error.
+pub fn parse_result(raw_response: &Value) -> Result<Map<String, Value>> {
+    if let Some(status) = raw_response["status"].as_str() {
+        if status == "error" {
+            return Err(ErrorKind::ExchangeError(format!("{}", raw_response["reason"])).into());
+        }
+    }
+
+    raw_response
+        .as_object()
+        .cloned()
+        .ok_or_else(|| ErrorKind::InvalidFieldFormat(format!("{}", raw_response)).into())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sign_params_matches_independently_computed_hmac() {
+        let params = HashMap::new();
+        let signed = sign_params("my_api_key", "my_customer_id", "my_secret", &params);
+
+        let nonce = signed.get("nonce").expect("nonce should be present").clone();
+        let message = format!("{}{}{}", nonce, "my_customer_id", "my_api_key");
+
+        let mut mac = HmacSha256::new_from_slice(b"my_secret").unwrap();
+        mac.update(message.as_bytes());
+        let expected = mac.finalize()
+            .into_bytes()
+            .iter()
+            .map(|byte| format!("{:02X}", byte))
+            .collect::<String>();
+
+        assert_eq!(signed.get("signature"), Some(&expected));
+        assert_eq!(signed.get("key"), Some(&"my_api_key".to_string()));
+    }
+
+    #[test]
+    fn sign_params_preserves_caller_supplied_params() {
+        let mut params = HashMap::new();
+        params.insert("amount", "1.5".to_string());
+
+        let signed = sign_params("key", "id", "secret", &params);
+
+        assert_eq!(signed.get("amount"), Some(&"1.5".to_string()));
+    }
+}
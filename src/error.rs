@@ -0,0 +1,29 @@
+//! Defines the error types used throughout the crate.
+
+error_chain! {
+    errors {
+        /// The pair is not supported by the exchange.
+        PairUnsupported {
+            description("pair is not supported")
+        }
+        /// A field returned by the exchange could not be parsed into the expected type.
+        InvalidFieldFormat(field: String) {
+            description("invalid field format")
+            display("invalid field format: '{}'", field)
+        }
+        /// A field expected in the exchange's response was not present.
+        MissingField(field: String) {
+            description("missing field")
+            display("missing field: '{}'", field)
+        }
+        /// A price is required for this order type but was not supplied.
+        MissingPrice {
+            description("a price must be supplied for limit orders")
+        }
+        /// The exchange returned an error message instead of the expected payload.
+        ExchangeError(message: String) {
+            description("exchange returned an error")
+            display("exchange returned an error: '{}'", message)
+        }
+    }
+}
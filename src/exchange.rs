@@ -0,0 +1,58 @@
+//! Defines the generic API implemented by every exchange in this crate.
+
+use error::*;
+use types::*;
+
+/// A uniform, safe interface over an exchange's REST API.
+///
+/// Every exchange module (e.g. `bitstamp::generic_api`) implements this trait for its
+/// exchange-specific client, so callers can write exchange-agnostic code.
+pub trait ExchangeApi {
+    /// Returns the ticker for `pair`.
+    fn ticker(&mut self, pair: Pair) -> Result<Ticker>;
+
+    /// Returns the order book for `pair`.
+    fn orderbook(&mut self, pair: Pair) -> Result<Orderbook>;
+
+    /// Returns the most recent public trades for `pair`.
+    fn trades(&mut self, pair: Pair) -> Result<Vec<Trade>>;
+
+    /// Returns the ticker for every pair the exchange supports.
+    ///
+    /// Where the exchange exposes a bulk endpoint this is a single call; otherwise
+    /// implementations may fall back to issuing one `ticker` call per supported pair, in
+    /// which case the whole batch fails as soon as one pair's call does.
+    fn all_tickers(&mut self) -> Result<Vec<Ticker>>;
+
+    /// Returns the best bid and ask currently on the book for `pair`, as
+    /// `(bid_price, bid_volume, ask_price, ask_volume)`.
+    fn book_ticker(&mut self, pair: Pair) -> Result<(Price, Volume, Price, Volume)>;
+
+    /// Places an order and returns information about it.
+    fn add_order(&mut self,
+                 order_type: OrderType,
+                 pair: Pair,
+                 quantity: Volume,
+                 price: Option<Price>)
+                 -> Result<OrderInfo>;
+
+    /// Performs the same validation as `add_order` but never places the order, returning an
+    /// `OrderInfo` with a synthetic identifier. Useful for backtesting and paper-trading
+    /// harnesses that want to exercise the real validation path without risking capital.
+    fn add_order_dry_run(&mut self,
+                          order_type: OrderType,
+                          pair: Pair,
+                          quantity: Volume,
+                          price: Option<Price>)
+                          -> Result<OrderInfo>;
+
+    /// Cancels a previously placed order. `pair` may be required by some exchanges to look up
+    /// the order and can be left to `None` otherwise.
+    fn cancel_order(&mut self, order_id: String, pair: Option<Pair>) -> Result<bool>;
+
+    /// Returns the current lifecycle status of a previously placed order.
+    fn order_status(&mut self, order_id: String) -> Result<OrderStatus>;
+
+    /// Returns the balances for each currency on the account.
+    fn balances(&mut self) -> Result<Balances>;
+}
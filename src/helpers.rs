@@ -0,0 +1,27 @@
+//! Small helpers shared by every exchange implementation.
+
+use std::fmt::Debug;
+use std::str::FromStr;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use serde_json::Value;
+
+use error::*;
+
+/// Parses a JSON value holding a decimal amount, which exchanges encode as a string rather
+/// than a native number to avoid transmitting an already `f64`-rounded value, into `T`.
+pub fn from_json_float<T>(value: &Value, field: &str) -> Result<T>
+    where T: FromStr,
+          T::Err: Debug
+{
+    value.as_str()
+        .ok_or_else(|| ErrorKind::InvalidFieldFormat(field.to_string()))?
+        .parse::<T>()
+        .map_err(|e| ErrorKind::InvalidFieldFormat(format!("{}: {:?}", field, e)).into())
+}
+
+/// Returns the current Unix timestamp, in milliseconds.
+pub fn get_unix_timestamp_ms() -> u64 {
+    let now = SystemTime::now().duration_since(UNIX_EPOCH).expect("system clock is before Unix epoch");
+    now.as_secs() * 1000 + u64::from(now.subsec_millis())
+}
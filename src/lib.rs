@@ -0,0 +1,18 @@
+//! Coinnect is a Rust library aiming to provide a complete and easy way to interact with a
+//! growing number of crypto-currency exchanges.
+
+#[macro_use]
+extern crate error_chain;
+extern crate bigdecimal;
+extern crate hmac;
+extern crate reqwest;
+extern crate serde_json;
+extern crate sha2;
+extern crate tungstenite;
+
+pub mod error;
+pub mod types;
+pub mod helpers;
+pub mod exchange;
+pub mod streaming;
+pub mod bitstamp;
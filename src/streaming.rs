@@ -0,0 +1,27 @@
+//! Defines the generic streaming API implemented by exchanges that expose a live, push-based
+//! feed alongside their REST API.
+
+use std::sync::mpsc::Receiver;
+
+use error::*;
+use types::*;
+
+/// A uniform interface over an exchange's live push feed.
+///
+/// Complements `ExchangeApi`: where that trait exposes one-shot blocking REST calls, this
+/// trait hands back a channel that keeps delivering fresh data until the caller drops the
+/// `Receiver` or the underlying connection fails. No reconnection is attempted: once the
+/// connection fails (or cannot be opened), the channel is closed.
+pub trait StreamingApi {
+    /// Subscribes to live ticker updates for `pair`.
+    ///
+    /// Each item is a `Result` rather than a bare `Ticker` so a message the implementation
+    /// failed to parse (a schema change, a transient decoding bug, ...) reaches the caller as
+    /// an `Err` instead of being silently dropped; the subscription otherwise keeps running.
+    fn subscribe_ticker(&mut self, pair: Pair) -> Result<Receiver<Result<Ticker>>>;
+
+    /// Subscribes to live order book updates for `pair`.
+    ///
+    /// See `subscribe_ticker` for why items are `Result`s.
+    fn subscribe_orderbook(&mut self, pair: Pair) -> Result<Receiver<Result<Orderbook>>>;
+}
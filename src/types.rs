@@ -0,0 +1,178 @@
+//! Defines the types shared by every exchange's generic API.
+
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+
+use bigdecimal::BigDecimal;
+
+/// A trading pair, e.g. `BTC_USD`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Pair {
+    BTC_USD,
+    BTC_EUR,
+    ETH_USD,
+    ETH_EUR,
+    ETH_BTC,
+}
+
+/// A currency held in an account balance.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Currency {
+    BTC,
+    ETH,
+    USD,
+    EUR,
+}
+
+/// Price of an asset, expressed in the quote currency of a pair.
+///
+/// Backed by an arbitrary-precision decimal rather than `f64` so that satoshi-level amounts
+/// parsed from an exchange's decimal strings don't pick up rounding drift.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct Price(pub BigDecimal);
+
+/// Volume of an asset, expressed in the base currency of a pair.
+///
+/// Backed by an arbitrary-precision decimal for the same reason as `Price`.
+#[derive(Debug, Clone, PartialEq, PartialOrd)]
+pub struct Volume(pub BigDecimal);
+
+macro_rules! impl_decimal_newtype {
+    ($name:ident) => {
+        impl From<f64> for $name {
+            /// Converts a finite `f64` into an exact decimal.
+            ///
+            /// `NaN` and the infinities have no decimal representation and `f64` legally
+            /// carries them, so rather than panic on those inputs this falls back to `0`;
+            /// callers that may see them should check `f64::is_finite()` beforehand.
+            fn from(value: f64) -> $name {
+                if !value.is_finite() {
+                    return $name(BigDecimal::from(0));
+                }
+
+                // f64's `to_string()` always yields a valid decimal literal once finite.
+                $name(BigDecimal::from_str(&value.to_string())
+                          .expect("finite f64 always has a valid decimal representation"))
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = <BigDecimal as FromStr>::Err;
+
+            fn from_str(s: &str) -> ::std::result::Result<$name, Self::Err> {
+                Ok($name(s.parse()?))
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                self.0.fmt(f)
+            }
+        }
+    }
+}
+
+impl_decimal_newtype!(Price);
+impl_decimal_newtype!(Volume);
+
+/// Balances for every currency held on an account.
+pub type Balances = HashMap<Currency, Volume>;
+
+/// A snapshot of an exchange's last trade, best bid and best ask for a pair.
+#[derive(Debug, Clone)]
+pub struct Ticker {
+    pub timestamp: u64,
+    pub pair: Pair,
+    pub last_trade_price: Price,
+    pub lowest_ask: Price,
+    pub highest_bid: Price,
+    pub volume: Option<Volume>,
+}
+
+/// A snapshot of an exchange's order book for a pair.
+///
+/// `asks` and `bids` are `(price, volume)` tuples, ordered as returned by the exchange.
+#[derive(Debug, Clone)]
+pub struct Orderbook {
+    pub timestamp: u64,
+    pub pair: Pair,
+    pub asks: Vec<(Price, Volume)>,
+    pub bids: Vec<(Price, Volume)>,
+}
+
+/// The kind of order to place through `ExchangeApi::add_order`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OrderType {
+    BuyLimit,
+    BuyMarket,
+    SellLimit,
+    SellMarket,
+}
+
+/// Information about an order that was just placed.
+#[derive(Debug, Clone)]
+pub struct OrderInfo {
+    pub timestamp: u64,
+    pub identifier: Vec<String>,
+}
+
+/// The lifecycle state of an order placed through `ExchangeApi::add_order`.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OrderStatus {
+    /// The order is still resting on the book, untouched.
+    Open,
+    /// Part of the order has been matched; carries the volume filled so far.
+    PartiallyFilled(Volume),
+    /// The order has been matched in full.
+    Filled,
+    /// The order was cancelled before being filled (in full or in part).
+    Cancelled,
+}
+
+/// The side of a public trade.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TradeType {
+    Buy,
+    Sell,
+}
+
+/// A single public trade that was executed on the exchange.
+#[derive(Debug, Clone)]
+pub struct Trade {
+    pub timestamp: u64,
+    pub pair: Pair,
+    pub price: Price,
+    pub amount: Volume,
+    pub trade_type: TradeType,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn price_from_f64_is_exact() {
+        assert_eq!(Price::from(0.00000001).to_string(), "0.00000001");
+        assert_eq!(Price::from(1234.5).to_string(), "1234.5");
+    }
+
+    #[test]
+    fn price_from_f64_does_not_panic_on_non_finite_values() {
+        assert_eq!(Price::from(::std::f64::NAN), Price(BigDecimal::from(0)));
+        assert_eq!(Price::from(::std::f64::INFINITY), Price(BigDecimal::from(0)));
+        assert_eq!(Price::from(::std::f64::NEG_INFINITY),
+                   Price(BigDecimal::from(0)));
+    }
+
+    #[test]
+    fn volume_from_str_parses_exchange_decimal_strings() {
+        let volume: Volume = "0.00000001".parse().unwrap();
+        assert_eq!(volume, Volume(BigDecimal::from_str("0.00000001").unwrap()));
+    }
+
+    #[test]
+    fn volume_from_str_rejects_malformed_input() {
+        assert!("not_a_number".parse::<Volume>().is_err());
+    }
+}